@@ -26,17 +26,95 @@ pub enum TokenType {
     Pool,
     RBrace,
     Semicolon,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LessThan,
+    Equals,
+    Tilde,
+    LParen,
+    RParen,
+    Colon,
+    Dot,
+    Comma,
+    At,
+    Assign,
+    Le,
+    DArrow,
     StringLiteral(String),
     Then,
     While,
 }
 
+impl TokenType {
+    /// Renders a token in the classic COOL lexer-dump style: a kind name
+    /// followed, for value-carrying tokens, by the payload.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::IntLiteral(n) => format!("INT_CONST {}", n),
+            Self::StringLiteral(s) => format!("STR_CONST {:?}", s),
+            Self::Ident(s) => format!("OBJECTID {}", s),
+            Self::ClassName(s) => format!("TYPEID {}", s),
+            Self::BoolLiteral(b) => format!("BOOL_CONST {}", b),
+            Self::Case => "CASE".into(),
+            Self::Class => "CLASS".into(),
+            Self::Else => "ELSE".into(),
+            Self::Esac => "ESAC".into(),
+            Self::Fi => "FI".into(),
+            Self::If => "IF".into(),
+            Self::In => "IN".into(),
+            Self::Inherits => "INHERITS".into(),
+            Self::IsVoid => "ISVOID".into(),
+            Self::Let => "LET".into(),
+            Self::Loop => "LOOP".into(),
+            Self::New => "NEW".into(),
+            Self::Not => "NOT".into(),
+            Self::Of => "OF".into(),
+            Self::Pool => "POOL".into(),
+            Self::Then => "THEN".into(),
+            Self::While => "WHILE".into(),
+            Self::LBrace => "'{'".into(),
+            Self::RBrace => "'}'".into(),
+            Self::LParen => "'('".into(),
+            Self::RParen => "')'".into(),
+            Self::Semicolon => "';'".into(),
+            Self::Colon => "':'".into(),
+            Self::Dot => "'.'".into(),
+            Self::Comma => "','".into(),
+            Self::At => "'@'".into(),
+            Self::Plus => "'+'".into(),
+            Self::Minus => "'-'".into(),
+            Self::Star => "'*'".into(),
+            Self::Slash => "'/'".into(),
+            Self::Tilde => "'~'".into(),
+            Self::LessThan => "'<'".into(),
+            Self::Equals => "'='".into(),
+            Self::Assign => "ASSIGN".into(),
+            Self::Le => "LE".into(),
+            Self::DArrow => "DARROW".into(),
+            Self::Illegal => "ILLEGAL".into(),
+            Self::Eof => "EOF".into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     token_type: TokenType,
     span: Span,
 }
 
+impl Token {
+    pub fn token_type(&self) -> &TokenType {
+        &self.token_type
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Span {
     start: Pos,
@@ -50,11 +128,25 @@ pub struct Pos {
     col: usize,
 }
 
+impl Span {
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    pub fn end(&self) -> Pos {
+        self.end
+    }
+}
+
 impl Pos {
     pub fn new(offset: usize, line: usize, col: usize) -> Self {
         Self { offset, line, col }
     }
 
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
     fn advance(&mut self, c: char) {
         self.offset += 1;
         if c == '\n' {
@@ -69,6 +161,7 @@ impl Pos {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LexerError {
     InvalidChar(char),
+    IntLiteralTooLong(String),
     StringConstantTooLong(usize),
     StringContainsNull,
     StringUnterminated,
@@ -81,6 +174,9 @@ impl std::fmt::Display for LexerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InvalidChar(c) => write!(f, "Invalid character: {}", c),
+            Self::IntLiteralTooLong(lit) => {
+                write!(f, "Integer literal out of range: {}", lit)
+            }
             Self::StringConstantTooLong(len) => {
                 write!(f, "String constant too long: {} chars", len)
             }
@@ -100,6 +196,8 @@ pub struct Lexer<'a> {
     current: Option<char>,
     pos: Pos,
     peek: Option<char>,
+    history: Vec<Token>,
+    cursor: usize,
 }
 
 impl<'a> Lexer<'a> {
@@ -112,11 +210,123 @@ impl<'a> Lexer<'a> {
             current: curr,
             pos: Pos::new(0, 1, 0),
             peek: None,
+            history: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the `n`th upcoming token (0-based) without consuming it, lexing
+    /// further from the source only as far as needed to satisfy the request.
+    /// Buffered tokens keep their [`Span`], so a later [`seek_back`] restores
+    /// both the token and its position. The EOF token is buffered once;
+    /// positions past it return `None`.
+    ///
+    /// [`seek_back`]: Lexer::seek_back
+    pub fn peek_token(&mut self, n: usize) -> Option<&Token> {
+        let idx = self.cursor + n;
+        while self.history.len() <= idx {
+            if self
+                .history
+                .last()
+                .is_some_and(|t| t.token_type == TokenType::Eof)
+            {
+                return None;
+            }
+            let token = self.produce();
+            let is_eof = token.token_type == TokenType::Eof;
+            self.history.push(token);
+            if is_eof {
+                break;
+            }
+        }
+
+        self.history.get(idx)
+    }
+
+    /// Consumes and returns the next token, advancing the lookahead cursor.
+    pub fn bump_token(&mut self) -> Option<Token> {
+        let token = self.peek_token(0).cloned();
+        if token.is_some() {
+            self.cursor += 1;
         }
+
+        token
+    }
+
+    /// Rewinds the cursor by `n` tokens so previously consumed tokens (and their
+    /// spans) can be re-read — used to undo a speculative parse.
+    pub fn seek_back(&mut self, n: usize) {
+        self.cursor = self.cursor.saturating_sub(n);
+    }
+
+    /// Lexes the next token, recovering from errors into an [`TokenType::Illegal`]
+    /// token so the lookahead buffer never stalls mid-stream.
+    fn produce(&mut self) -> Token {
+        let start = self.pos;
+        match self.next_token() {
+            Ok(token) => token,
+            Err(_) => {
+                if self.current.is_some() && self.pos.offset == start.offset {
+                    self.advance();
+                }
+                Token {
+                    token_type: TokenType::Illegal,
+                    span: Span {
+                        start,
+                        end: self.pos,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Lexes `src` to completion, collecting every token and every diagnostic
+    /// in a single pass. Unlike iterating over [`Lexer`] and bailing on the
+    /// first [`Err`], this recovers from each lexical error by emitting an
+    /// [`TokenType::Illegal`] placeholder (so token positions stay aligned) and
+    /// recording the error alongside the [`Span`] it occurred at.
+    pub fn tokenize(src: &'a str) -> (Vec<Token>, Vec<(LexerError, Span)>) {
+        let mut lexer = Lexer::new(src);
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        loop {
+            // Skip trivia first so an error's span starts at the offending
+            // input rather than at the whitespace or comments before it.
+            let trivia = lexer.skip_trivia();
+            let start = lexer.pos;
+            match trivia.and_then(|()| lexer.next_token()) {
+                Ok(token) => {
+                    let is_eof = token.token_type == TokenType::Eof;
+                    tokens.push(token);
+                    if is_eof {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    // Guarantee forward progress even for errors raised before
+                    // any char was consumed, so the loop can reach EOF.
+                    if lexer.current.is_some() && lexer.pos.offset == start.offset {
+                        lexer.advance();
+                    }
+                    let span = Span {
+                        start,
+                        end: lexer.pos,
+                    };
+                    errors.push((err, span));
+                    tokens.push(Token {
+                        token_type: TokenType::Illegal,
+                        span,
+                    });
+                }
+            }
+        }
+
+        (tokens, errors)
     }
 
     pub fn next_token(&mut self) -> Result<Token, LexerError> {
-        self.skip_whitespace();
+        self.skip_trivia()?;
         let start = self.pos;
 
         let token_type = match self.current {
@@ -130,10 +340,88 @@ impl<'a> Lexer<'a> {
                     self.advance();
                     TokenType::RBrace
                 }
+                '+' => {
+                    self.advance();
+                    TokenType::Plus
+                }
+                '-' => {
+                    self.advance();
+                    TokenType::Minus
+                }
+                '*' => {
+                    self.advance();
+                    TokenType::Star
+                }
+                '/' => {
+                    self.advance();
+                    TokenType::Slash
+                }
+                '~' => {
+                    self.advance();
+                    TokenType::Tilde
+                }
+                '(' => {
+                    self.advance();
+                    TokenType::LParen
+                }
+                ')' => {
+                    self.advance();
+                    TokenType::RParen
+                }
+                ':' => {
+                    self.advance();
+                    TokenType::Colon
+                }
+                '.' => {
+                    self.advance();
+                    TokenType::Dot
+                }
+                ',' => {
+                    self.advance();
+                    TokenType::Comma
+                }
+                '@' => {
+                    self.advance();
+                    TokenType::At
+                }
+                ';' => {
+                    self.advance();
+                    TokenType::Semicolon
+                }
+                '<' => {
+                    self.advance();
+                    match self.current {
+                        Some('-') => {
+                            self.advance();
+                            TokenType::Assign
+                        }
+                        Some('=') => {
+                            self.advance();
+                            TokenType::Le
+                        }
+                        _ => TokenType::LessThan,
+                    }
+                }
+                '=' => {
+                    self.advance();
+                    match self.current {
+                        Some('>') => {
+                            self.advance();
+                            TokenType::DArrow
+                        }
+                        _ => TokenType::Equals,
+                    }
+                }
                 '"' => self.tokenize_string()?,
                 '0'..='9' => self.tokenize_number()?,
                 'a'..='z' | 'A'..='Z' | '_' => self.tokenize_ident()?,
-                c => return Err(LexerError::InvalidChar(c)),
+                c => {
+                    // Consume the offending char so the cursor always makes
+                    // progress. `tokenize` and the lookahead buffer recover by
+                    // emitting an `Illegal` token; the iterator yields the error.
+                    self.advance();
+                    return Err(LexerError::InvalidChar(c));
+                }
             },
         };
 
@@ -155,6 +443,59 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Skips whitespace and COOL comments, so both are treated uniformly as
+    /// inter-token trivia. Line comments (`-- ...`) run to the next newline or
+    /// EOF; block comments (`(* ... *)`) nest, and a bare `*)` outside any
+    /// comment is reported as [`LexerError::UnmatchedComment`].
+    fn skip_trivia(&mut self) -> Result<(), LexerError> {
+        loop {
+            self.skip_whitespace();
+            let (c, p) = (self.current, self.peek());
+            match c {
+                Some('-') if p == Some('-') => {
+                    while let Some(ch) = self.current {
+                        if ch == '\n' {
+                            break;
+                        }
+                        self.advance();
+                    }
+                }
+                Some('(') if p == Some('*') => self.skip_block_comment()?,
+                Some('*') if p == Some(')') => {
+                    return Err(LexerError::UnmatchedComment);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), LexerError> {
+        self.advance();
+        self.advance();
+        let mut depth = 1usize;
+        while depth > 0 {
+            let (c, p) = (self.current, self.peek());
+            match c {
+                None => return Err(LexerError::UnterminatedComment),
+                Some('(') if p == Some('*') => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                Some('*') if p == Some(')') => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn advance(&mut self) -> Option<char> {
         let curr = self.current;
         if let Some(c) = curr {
@@ -190,11 +531,36 @@ impl<'a> Lexer<'a> {
                 }
                 '\0' => return Err(LexerError::StringContainsNull),
                 '\n' => return Err(LexerError::StringUnterminated),
+                '\\' => {
+                    self.advance();
+                    match self.current {
+                        None => return Err(LexerError::StringContainsEof),
+                        Some('\0') => return Err(LexerError::StringContainsNull),
+                        Some(esc) => {
+                            let decoded = match esc {
+                                'n' => '\n',
+                                't' => '\t',
+                                'b' => '\u{8}',
+                                'f' => '\u{c}',
+                                // A backslash before a real newline splices the
+                                // line continuation into the literal.
+                                other => other,
+                            };
+                            string.push(decoded);
+                            self.advance();
+                        }
+                    }
+                }
                 ch => {
                     string.push(ch);
                     self.advance();
                 }
             }
+
+            let len = string.chars().count();
+            if len > 1024 {
+                return Err(LexerError::StringConstantTooLong(len));
+            }
         }
 
         Err(LexerError::StringContainsEof)
@@ -212,7 +578,7 @@ impl<'a> Lexer<'a> {
 
         num.parse::<i64>()
             .map(TokenType::IntLiteral)
-            .map_err(|_| LexerError::InvalidChar('0'))
+            .map_err(|_| LexerError::IntLiteralTooLong(num))
     }
 
     fn tokenize_ident(&mut self) -> Result<TokenType, LexerError> {
@@ -226,9 +592,20 @@ impl<'a> Lexer<'a> {
             self.advance();
         }
 
-        let id = match ident.as_str() {
-            "true" => TokenType::BoolLiteral(true),
-            "false" => TokenType::BoolLiteral(false),
+        let lowered = ident.to_ascii_lowercase();
+
+        // Keywords are case-insensitive, but the boolean literals `true`/`false`
+        // must begin with a lowercase letter; their trailing letters remain
+        // case-insensitive, so match the folded spelling gated on the first char.
+        if ident.starts_with(char::is_lowercase) {
+            match lowered.as_str() {
+                "true" => return Ok(TokenType::BoolLiteral(true)),
+                "false" => return Ok(TokenType::BoolLiteral(false)),
+                _ => {}
+            }
+        }
+
+        let id = match lowered.as_str() {
             "if" => TokenType::If,
             "fi" => TokenType::Fi,
             "else" => TokenType::Else,
@@ -263,6 +640,7 @@ impl<'a> Iterator for Lexer<'a> {
     type Item = Result<Token, LexerError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
         match self.next_token() {
             Ok(token) => {
                 if token.token_type == TokenType::Eof {
@@ -271,7 +649,14 @@ impl<'a> Iterator for Lexer<'a> {
                     Some(Ok(token))
                 }
             }
-            Err(e) => Some(Err(e)),
+            Err(e) => {
+                // Never stall on the offending input: if nothing was consumed,
+                // skip one char so the next poll advances toward EOF.
+                if self.current.is_some() && self.pos.offset == start.offset {
+                    self.advance();
+                }
+                Some(Err(e))
+            }
         }
     }
 }
@@ -2,6 +2,8 @@ use clap::Parser;
 
 mod lexer;
 
+use lexer::{Lexer, TokenType};
+
 #[derive(Parser, Debug)]
 struct Args {
     #[arg(long, group = "op")]
@@ -17,7 +19,33 @@ fn main() {
         std::process::exit(1);
     }
 
-    if args.lex {
-        let _input_file = args.input;
+    let source = match std::fs::read_to_string(&args.input) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", args.input, e);
+            std::process::exit(1);
+        }
+    };
+
+    let (tokens, errors) = Lexer::tokenize(&source);
+    let mut diagnostics = errors.iter();
+    let mut had_error = false;
+
+    for token in &tokens {
+        let line = token.span().start().line();
+        match token.token_type() {
+            TokenType::Eof => {}
+            TokenType::Illegal => {
+                had_error = true;
+                if let Some((err, _)) = diagnostics.next() {
+                    println!("#{} ERROR \"{}\"", line, err);
+                }
+            }
+            token_type => println!("#{} {}", line, token_type.describe()),
+        }
+    }
+
+    if had_error {
+        std::process::exit(1);
     }
 }